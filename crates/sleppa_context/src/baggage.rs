@@ -0,0 +1,199 @@
+//! Baggage module.
+//!
+//! Baggage is a string-keyed bag of ad-hoc metadata carried alongside the typed properties of a
+//! [Context], mirroring OpenTelemetry baggage. It exists for cross-cutting values that don't
+//! warrant a dedicated Rust type of their own, while still benefiting from the same immutable
+//! copy-on-write semantics. Baggage crosses a process boundary as a W3C baggage header, written
+//! with [`Context::baggage_header`] and read back with [`Context::with_baggage_header`]; a
+//! [Propagator](crate::propagation::Propagator) does not carry baggage, since only
+//! [PropagatableProperty](crate::propagation::PropagatableProperty) types can be registered on it.
+
+use crate::context::Context;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// The property [Baggage] is stored under on a [Context].
+///
+/// Baggage is just another property, appended and read through the very same
+/// [`with_property`](Context::with_property) / [`get`](Context::get) mechanism every other
+/// property uses; this type only exists to give that reserved slot a name.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Baggage(HashMap<String, String>);
+
+impl Context {
+    /// Returns a copy of the context with the given baggage key/value pair set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sleppa_context::Context;
+    ///
+    /// let context = Context::new().with_baggage("user.id", "42");
+    ///
+    /// assert_eq!(context.get_baggage("user.id"), Some("42"));
+    /// ```
+    pub fn with_baggage(&self, key: impl Into<String>, value: impl Into<String>) -> Context {
+        let mut baggage = self.get::<Baggage>().cloned().unwrap_or_default();
+        baggage.0.insert(key.into(), value.into());
+
+        self.with_property(baggage)
+    }
+
+    /// Returns the baggage value for the given key, if any.
+    pub fn get_baggage(&self, key: &str) -> Option<&str> {
+        self.get::<Baggage>()
+            .and_then(|baggage| baggage.0.get(key))
+            .map(String::as_str)
+    }
+
+    /// Returns an iterator over every baggage key/value pair set on this context.
+    pub fn iter_baggage(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.get::<Baggage>()
+            .into_iter()
+            .flat_map(|baggage| baggage.0.iter())
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Serializes this context's baggage to the W3C `key1=value1,key2=value2` wire format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sleppa_context::Context;
+    ///
+    /// let context = Context::new().with_baggage("user.id", "42");
+    ///
+    /// assert_eq!(context.baggage_header(), "user.id=42");
+    /// ```
+    pub fn baggage_header(&self) -> String {
+        let mut header = String::new();
+
+        for (key, value) in self.iter_baggage() {
+            if !header.is_empty() {
+                header.push(',');
+            }
+
+            let _ = write!(header, "{key}={}", percent_encode(value));
+        }
+
+        header
+    }
+
+    /// Returns a copy of the context with baggage parsed out of a W3C baggage header merged in.
+    ///
+    /// Members that aren't valid `key=value` pairs are ignored rather than failing the whole
+    /// parse.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sleppa_context::Context;
+    ///
+    /// let context = Context::new().with_baggage_header("user.id=42, region=eu%20west");
+    ///
+    /// assert_eq!(context.get_baggage("user.id"), Some("42"));
+    /// assert_eq!(context.get_baggage("region"), Some("eu west"));
+    /// ```
+    pub fn with_baggage_header(&self, header: &str) -> Context {
+        header
+            .split(',')
+            .filter_map(|member| member.split_once('='))
+            .fold(self.clone(), |context, (key, value)| {
+                context.with_baggage(key.trim(), percent_decode(value.trim()))
+            })
+    }
+}
+
+/// Percent-encodes a baggage value, operating on raw bytes so that non-ASCII UTF-8 text survives
+/// the round trip rather than being truncated to one `char` per byte.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        if byte.is_ascii() && !matches!(byte, b',' | b';' | b'=' | b'%') {
+            encoded.push(byte as char);
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+    }
+
+    encoded
+}
+
+/// Percent-decodes a baggage value, leaving malformed escapes untouched.
+///
+/// Decoding is done on raw bytes, collected and converted back to `String` with
+/// [`String::from_utf8_lossy`], so that multi-byte UTF-8 sequences (each byte percent-encoded
+/// individually by [`percent_encode`]) are reassembled correctly instead of being decoded one byte
+/// at a time into a `char`.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && i + 2 < bytes.len()
+            && bytes[i + 1].is_ascii_hexdigit()
+            && bytes[i + 2].is_ascii_hexdigit()
+        {
+            let hex = std::str::from_utf8(&bytes[i + 1..=i + 2]).ok();
+            if let Some(byte) = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_baggage_is_copy_on_write() {
+        let base = Context::new().with_baggage("a", "1");
+        let derived = base.with_baggage("b", "2");
+
+        assert_eq!(base.get_baggage("b"), None);
+        assert_eq!(derived.get_baggage("a"), Some("1"));
+        assert_eq!(derived.get_baggage("b"), Some("2"));
+    }
+
+    #[test]
+    fn test_baggage_header_round_trip() {
+        let context = Context::new()
+            .with_baggage("user.id", "42")
+            .with_baggage("region", "eu west");
+
+        let header = context.baggage_header();
+        let parsed = Context::new().with_baggage_header(&header);
+
+        assert_eq!(parsed.get_baggage("user.id"), Some("42"));
+        assert_eq!(parsed.get_baggage("region"), Some("eu west"));
+    }
+
+    #[test]
+    fn test_with_baggage_header_ignores_malformed_members() {
+        let context = Context::new().with_baggage_header("valid=1, no-equals-sign, ,also=2");
+
+        assert_eq!(context.get_baggage("valid"), Some("1"));
+        assert_eq!(context.get_baggage("also"), Some("2"));
+    }
+
+    #[test]
+    fn test_baggage_header_round_trip_preserves_non_ascii_values() {
+        let context = Context::new().with_baggage("name", "café");
+
+        let header = context.baggage_header();
+        let parsed = Context::new().with_baggage_header(&header);
+
+        assert_eq!(parsed.get_baggage("name"), Some("café"));
+    }
+}
@@ -1,15 +1,14 @@
 use crate::guard::ContextGuard;
+use arc_swap::ArcSwap;
+use im::HashMap;
 use std::any::{Any, TypeId};
-use std::cell::RefCell;
-use std::collections::HashMap;
 use std::fmt;
 use std::hash::{BuildHasherDefault, Hasher};
 use std::marker::PhantomData;
 use std::sync::Arc;
 
 thread_local! {
-    pub static CURRENT_CONTEXT: RefCell<Context> = RefCell::new(Context::default());
-    pub static DEFAULT_CONTEXT: Context = Context::default();
+    pub static CURRENT_CONTEXT: ArcSwap<Context> = ArcSwap::from_pointee(Context::default());
 }
 
 /// Execution context structure.
@@ -18,20 +17,24 @@ thread_local! {
 /// a thread-safe propagation mechanism used for sharing values (or properties) between logically
 /// associated execution units.
 /// When writing data to an execution [Context], the latter is cloned and the new property is
-/// appended to it (i.e. a kind of copy-on-write pattern).
+/// appended to it (i.e. a kind of copy-on-write pattern). The backing map is a persistent
+/// hash-array-mapped trie, so that clone is O(1) (it only shares the trie root) and appending a
+/// property is O(log n), structurally sharing every untouched subtree with the parent context
+/// instead of copying the whole map.
 #[derive(Clone, Default)]
 pub struct Context {
     properties: HashMap<TypeId, Arc<dyn Any + Sync + Send>, BuildHasherDefault<TypeIdHasher>>,
 }
 
-/// Executes a closure with a reference to this thread's current context.
+/// Returns this thread's current context as a cheaply-clonable `Arc`.
 ///
-/// Note: This function will panic if you attempt to attach another context
-/// while the context is still borrowed.
-fn get_current_context<F: FnMut(&Context) -> T, T>(mut f: F) -> T {
+/// Reading the current context is a lock-free atomic load followed by a single refcount bump,
+/// so it can safely be called re-entrantly, e.g. from within a closure that is itself reading
+/// the context.
+fn get_current_context() -> Arc<Context> {
     CURRENT_CONTEXT
-        .try_with(|context| f(&context.borrow()))
-        .unwrap_or_else(|_| DEFAULT_CONTEXT.with(|cx| f(cx)))
+        .try_with(|current| current.load_full())
+        .unwrap_or_else(|_| Arc::new(Context::default()))
 }
 
 impl Context {
@@ -43,7 +46,10 @@ impl Context {
         Context::default()
     }
 
-    /// Returns an immutable clone of the current thread's context.
+    /// Returns this thread's current context.
+    ///
+    /// This is a lock-free atomic load followed by a single refcount bump on the returned `Arc`,
+    /// not a deep clone of the underlying properties.
     ///
     /// # Examples
     ///
@@ -73,8 +79,8 @@ impl Context {
     /// // Do some work on the context
     /// access_current_context()
     /// ```
-    pub fn current() -> Self {
-        get_current_context(|context| context.clone())
+    pub fn current() -> Arc<Context> {
+        get_current_context()
     }
 
     /// Binds the context to the current thread.
@@ -116,7 +122,7 @@ impl Context {
     /// ```
     pub fn bind(self) -> ContextGuard {
         let previous_context = CURRENT_CONTEXT
-            .try_with(|current| current.replace(self))
+            .try_with(|current| current.swap(Arc::new(self)))
             .ok();
 
         ContextGuard {
@@ -178,6 +184,83 @@ impl Context {
             .get(&TypeId::of::<T>())
             .and_then(|rc| rc.downcast_ref())
     }
+
+    /// Returns a copy of the context with the property of type `T` removed, if it was set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sleppa_context::Context;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct UserId(u64);
+    ///
+    /// let context = Context::new().with_property(UserId(42));
+    /// let cleared = context.without_property::<UserId>();
+    ///
+    /// assert_eq!(context.get::<UserId>(), Some(&UserId(42)));
+    /// assert_eq!(cleared.get::<UserId>(), None);
+    /// ```
+    pub fn without_property<T: 'static>(&self) -> Self {
+        let mut context = self.clone();
+        context.properties.remove(&TypeId::of::<T>());
+        context
+    }
+
+    /// Returns a copy of `self` with every property of `other` overlaid onto it.
+    ///
+    /// Neither `self` nor `other` is mutated; where both contexts set the same property, `other`'s
+    /// value wins.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sleppa_context::Context;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct UserId(u64);
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct RequestId(&'static str);
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct TraceId(&'static str);
+    ///
+    /// // `base` has more properties than `overlay`; `other`'s value must still win.
+    /// let base = Context::new()
+    ///     .with_property(UserId(1))
+    ///     .with_property(RequestId("abc"))
+    ///     .with_property(TraceId("t-1"));
+    /// let overlay = Context::new().with_property(UserId(2));
+    ///
+    /// let merged = base.merge(&overlay);
+    ///
+    /// assert_eq!(merged.get::<UserId>(), Some(&UserId(2)));
+    /// assert_eq!(merged.get::<RequestId>(), Some(&RequestId("abc")));
+    /// assert_eq!(merged.get::<TraceId>(), Some(&TraceId("t-1")));
+    /// ```
+    pub fn merge(&self, other: &Self) -> Self {
+        // `im::HashMap::union` picks whichever operand is larger as the one whose values win on
+        // collision, not the receiver, so it can't express "other always wins" on its own.
+        // `union_with` calls the closure as `(value in self, value in other)` regardless of
+        // either map's size, so always returning the right-hand side gives `other` priority.
+        Context {
+            properties: self
+                .properties
+                .clone()
+                .union_with(other.properties.clone(), |_, other_value| other_value),
+        }
+    }
+
+    /// Returns the number of properties set on this context.
+    pub fn len(&self) -> usize {
+        self.properties.len()
+    }
+
+    /// Returns `true` if this context has no property set.
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
 }
 
 impl fmt::Debug for Context {
@@ -240,4 +323,106 @@ mod tests {
         // Query yet unset repository user property (where None should be returned)
         assert_eq!(context.get::<RepositoryUser>(), None);
     }
+
+    #[test]
+    fn test_current_context_can_be_read_reentrantly_while_previously_borrowed() {
+        #[derive(Debug, PartialEq)]
+        struct UserId(u64);
+
+        // Hold on to a previously returned `Arc<Context>` ...
+        let outer = Context::current();
+
+        let _guard = Context::new().with_property(UserId(42)).bind();
+
+        // ... and read `current()` again while still holding it. With the old
+        // `thread_local! RefCell<Context>` storage this would panic if `outer` were a live
+        // `Ref`/borrow; `ArcSwap` makes this just another atomic load.
+        let inner = Context::current();
+        assert_eq!(inner.get::<UserId>(), Some(&UserId(42)));
+        assert_eq!(outer.get::<UserId>(), None);
+
+        drop(_guard);
+        assert_eq!(Context::current().get::<UserId>(), None);
+    }
+
+    #[test]
+    fn test_without_property_removes_only_the_given_type() {
+        #[derive(Debug, PartialEq)]
+        struct UserId(u64);
+
+        #[derive(Debug, PartialEq)]
+        struct RequestId(&'static str);
+
+        let context = Context::new()
+            .with_property(UserId(42))
+            .with_property(RequestId("abc"));
+
+        let cleared = context.without_property::<UserId>();
+
+        assert_eq!(cleared.get::<UserId>(), None);
+        assert_eq!(cleared.get::<RequestId>(), Some(&RequestId("abc")));
+        // The original context is untouched.
+        assert_eq!(context.get::<UserId>(), Some(&UserId(42)));
+    }
+
+    #[test]
+    fn test_merge_overlays_other_onto_self_with_other_winning_collisions() {
+        #[derive(Debug, PartialEq)]
+        struct UserId(u64);
+
+        #[derive(Debug, PartialEq)]
+        struct RequestId(&'static str);
+
+        let base = Context::new().with_property(UserId(1));
+        let overlay = Context::new()
+            .with_property(UserId(2))
+            .with_property(RequestId("abc"));
+
+        let merged = base.merge(&overlay);
+
+        assert_eq!(merged.get::<UserId>(), Some(&UserId(2)));
+        assert_eq!(merged.get::<RequestId>(), Some(&RequestId("abc")));
+        // Neither input was mutated.
+        assert_eq!(base.get::<UserId>(), Some(&UserId(1)));
+        assert_eq!(base.get::<RequestId>(), None);
+    }
+
+    #[test]
+    fn test_merge_other_wins_collision_even_when_self_has_more_properties() {
+        #[derive(Debug, PartialEq)]
+        struct UserId(u64);
+
+        #[derive(Debug, PartialEq)]
+        struct RequestId(&'static str);
+
+        #[derive(Debug, PartialEq)]
+        struct TraceId(&'static str);
+
+        // `base` has strictly more properties than `overlay`, which must not flip who wins.
+        let base = Context::new()
+            .with_property(UserId(1))
+            .with_property(RequestId("abc"))
+            .with_property(TraceId("t-1"));
+        let overlay = Context::new().with_property(UserId(2));
+
+        let merged = base.merge(&overlay);
+
+        assert_eq!(merged.get::<UserId>(), Some(&UserId(2)));
+        assert_eq!(merged.get::<RequestId>(), Some(&RequestId("abc")));
+        assert_eq!(merged.get::<TraceId>(), Some(&TraceId("t-1")));
+    }
+
+    #[test]
+    fn test_len_and_is_empty() {
+        #[derive(Debug, PartialEq)]
+        struct UserId(u64);
+
+        let context = Context::new();
+        assert_eq!(context.len(), 0);
+        assert!(context.is_empty());
+
+        let context = context.with_property(UserId(42));
+        assert_eq!(context.len(), 1);
+        assert!(!context.is_empty());
+    }
 }
@@ -0,0 +1,194 @@
+//! Async context propagation module.
+//!
+//! A [ContextGuard](crate::guard::ContextGuard) restores the previous context on `Drop`, which
+//! works for synchronous scopes but breaks across `.await` points: once a future yields, whatever
+//! thread later resumes it has its own current context. [FutureExt] and [StreamExt] close that
+//! gap by binding a [Context] for the duration of each poll, so `Context::current()` resolves
+//! correctly inside the awaited work regardless of which executor thread actually drives it.
+
+use crate::context::Context;
+use futures_core::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context as TaskContext, Poll};
+
+/// A future (or stream) with a [Context] bound to it for the duration of every poll.
+///
+/// Built through [`FutureExt::with_context`] / [`StreamExt::with_context`] (or their
+/// `with_current_context` counterparts).
+#[allow(missing_debug_implementations)]
+pub struct WithContext<T> {
+    inner: T,
+    cx: Context,
+}
+
+impl<F: Future> Future for WithContext<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, task_cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        // Safety: `inner` is never moved out of, only polled in place.
+        let this = unsafe { self.get_unchecked_mut() };
+        let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+
+        let _guard = this.cx.clone().bind();
+        inner.poll(task_cx)
+    }
+}
+
+impl<S: Stream> Stream for WithContext<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, task_cx: &mut TaskContext<'_>) -> Poll<Option<Self::Item>> {
+        // Safety: `inner` is never moved out of, only polled in place.
+        let this = unsafe { self.get_unchecked_mut() };
+        let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+
+        let _guard = this.cx.clone().bind();
+        inner.poll_next(task_cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Extension trait binding a [Context] to a [Future] for the duration of each poll.
+pub trait FutureExt: Future + Sized {
+    /// Binds `cx` to the current thread every time this future is polled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use sleppa_context::Context;
+    /// use sleppa_context::future::FutureExt;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct UserId(u64);
+    ///
+    /// # futures_executor::block_on(async {
+    /// let cx = Context::new().with_property(UserId(42));
+    ///
+    /// let user_id = async { Context::current().get::<UserId>().map(|u| u.0) }
+    ///     .with_context(cx)
+    ///     .await;
+    ///
+    /// assert_eq!(user_id, Some(42));
+    /// # });
+    /// ```
+    fn with_context(self, cx: Context) -> WithContext<Self> {
+        WithContext { inner: self, cx }
+    }
+
+    /// Binds a snapshot of [`Context::current`] to the current thread every time this future is
+    /// polled.
+    fn with_current_context(self) -> WithContext<Self> {
+        let cx = (*Context::current()).clone();
+        self.with_context(cx)
+    }
+}
+
+impl<F: Future> FutureExt for F {}
+
+/// Extension trait binding a [Context] to a [Stream] for the duration of each poll.
+pub trait StreamExt: Stream + Sized {
+    /// Binds `cx` to the current thread every time this stream is polled.
+    fn with_context(self, cx: Context) -> WithContext<Self> {
+        WithContext { inner: self, cx }
+    }
+
+    /// Binds a snapshot of [`Context::current`] to the current thread every time this stream is
+    /// polled.
+    fn with_current_context(self) -> WithContext<Self> {
+        let cx = (*Context::current()).clone();
+        self.with_context(cx)
+    }
+}
+
+impl<S: Stream> StreamExt for S {}
+
+#[cfg(test)]
+mod tests {
+    use crate::future::{FutureExt, StreamExt};
+    use crate::*;
+    use futures_core::Stream;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context as TaskContext, Poll, RawWaker, RawWakerVTable, Waker};
+
+    #[derive(Debug, PartialEq)]
+    struct UserId(u64);
+
+    /// A minimal stream yielding a fixed number of items, used to exercise [StreamExt] without
+    /// pulling in a full executor's worth of combinators. Each item is the [UserId] value found on
+    /// the current context while that item was being polled.
+    struct Counter(u8);
+
+    impl Stream for Counter {
+        type Item = Option<u64>;
+
+        fn poll_next(
+            mut self: Pin<&mut Self>,
+            _: &mut TaskContext<'_>,
+        ) -> Poll<Option<Option<u64>>> {
+            if self.0 == 0 {
+                Poll::Ready(None)
+            } else {
+                self.0 -= 1;
+                Poll::Ready(Some(Context::current().get::<UserId>().map(|u| u.0)))
+            }
+        }
+    }
+
+    /// A waker that does nothing, enough to drive a future/stream that never actually yields.
+    fn noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker {
+            raw()
+        }
+        fn noop(_: *const ()) {}
+        fn raw() -> RawWaker {
+            static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+
+        unsafe { Waker::from_raw(raw()) }
+    }
+
+    #[test]
+    fn test_with_context_is_visible_inside_the_polled_future() {
+        let cx = Context::new().with_property(UserId(42));
+
+        let future = async { Context::current().get::<UserId>().map(|u| u.0) }.with_context(cx);
+        let mut future = Box::pin(future);
+
+        let waker = noop_waker();
+        let mut task_cx = TaskContext::from_waker(&waker);
+
+        let user_id = match future.as_mut().poll(&mut task_cx) {
+            Poll::Ready(value) => value,
+            Poll::Pending => panic!("future should resolve on first poll"),
+        };
+        assert_eq!(user_id, Some(42));
+
+        // The binding only lived for the duration of the poll.
+        assert_eq!(Context::current().get::<UserId>(), None);
+    }
+
+    #[test]
+    fn test_stream_with_context_binds_context_on_every_poll() {
+        let cx = Context::new().with_property(UserId(7));
+
+        let mut stream = Box::pin(Counter(2).with_context(cx));
+
+        let waker = noop_waker();
+        let mut task_cx = TaskContext::from_waker(&waker);
+
+        let mut polled = 0;
+        while let Poll::Ready(Some(seen)) = stream.as_mut().poll_next(&mut task_cx) {
+            polled += 1;
+            // The context is visible inside the poll, and unbound again once it returns.
+            assert_eq!(seen, Some(7));
+            assert_eq!(Context::current().get::<UserId>(), None);
+        }
+        assert_eq!(polled, 2);
+    }
+}
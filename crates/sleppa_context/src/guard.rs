@@ -3,6 +3,7 @@
 use crate::context::Context;
 
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// Context guard data structure.
 ///
@@ -12,7 +13,7 @@ use std::marker::PhantomData;
 /// [Context::bind] method.
 #[allow(missing_debug_implementations)]
 pub struct ContextGuard {
-    pub previous_context: Option<Context>,
+    pub previous_context: Option<Arc<Context>>,
 
     // ensure this type is !Send as it relies on thread locals
     pub _marker: PhantomData<*const ()>,
@@ -21,8 +22,8 @@ pub struct ContextGuard {
 impl Drop for ContextGuard {
     fn drop(&mut self) {
         if let Some(previous_context) = self.previous_context.take() {
-            let _ = crate::context::CURRENT_CONTEXT
-                .try_with(|current| current.replace(previous_context));
+            let _ =
+                crate::context::CURRENT_CONTEXT.try_with(|current| current.swap(previous_context));
         }
     }
 }
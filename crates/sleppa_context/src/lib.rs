@@ -7,9 +7,12 @@
 //! on write pattern).
 
 // Declare package modules
+pub mod baggage;
 pub mod constants;
 pub mod context;
+pub mod future;
 pub mod guard;
+pub mod propagation;
 
 // Export package modules
 pub use crate::context::Context;
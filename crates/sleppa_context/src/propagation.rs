@@ -0,0 +1,191 @@
+//! Cross-boundary context propagation module.
+//!
+//! A [Context] normally lives in-process, carried around as `Arc<dyn Any>` values keyed by
+//! `TypeId`. Those values cannot be serialized in any generic way, so they are of no use once a
+//! process (or plugin) boundary is actually crossed over the wire. This module borrows the
+//! propagator pattern popularized by OpenTelemetry: a [Context] property opts into propagation by
+//! implementing [PropagatableProperty], and a [Propagator] is built by registering the property
+//! types that should travel across the boundary, reading from (or writing to) a text-based
+//! carrier such as HTTP headers or a message envelope.
+
+use crate::context::Context;
+use std::collections::HashMap;
+
+/// A write-only destination for propagated key/value pairs.
+///
+/// Typically implemented on top of whatever carries the wire metadata, e.g. HTTP headers or a
+/// message envelope.
+pub trait Injector {
+    /// Sets a key with the given value.
+    fn set(&mut self, key: &str, value: String);
+}
+
+/// A read-only source of propagated key/value pairs.
+///
+/// Typically implemented on top of whatever carries the wire metadata, e.g. HTTP headers or a
+/// message envelope.
+pub trait Extractor {
+    /// Returns the value associated with the given key, if any.
+    fn get(&self, key: &str) -> Option<&str>;
+}
+
+impl Injector for HashMap<String, String> {
+    fn set(&mut self, key: &str, value: String) {
+        self.insert(key.to_owned(), value);
+    }
+}
+
+impl Extractor for HashMap<String, String> {
+    fn get(&self, key: &str) -> Option<&str> {
+        HashMap::get(self, key).map(String::as_str)
+    }
+}
+
+/// A [Context] property that can be propagated across a process (or plugin) boundary.
+///
+/// Property types opt into propagation by implementing this trait and registering themselves on
+/// a [Propagator], which then knows how to read and write them as plain text.
+pub trait PropagatableProperty: Sized + 'static + Send + Sync {
+    /// The carrier key this property is written under.
+    const KEY: &'static str;
+
+    /// Serializes this property to its text representation.
+    fn to_text(&self) -> String;
+
+    /// Parses a property back from its text representation.
+    ///
+    /// Returns `None` if `text` isn't a valid representation of the property, in which case the
+    /// property is left unset rather than failing the whole extraction.
+    fn from_text(text: &str) -> Option<Self>;
+}
+
+/// The operations a [Propagator] stores for each registered property type: one writing it from a
+/// [Context] into a carrier, and one reading it from a carrier onto a [Context].
+type InjectFn = Box<dyn Fn(&Context, &mut dyn Injector) + Send + Sync>;
+type ExtractFn = Box<dyn Fn(&dyn Extractor, Context) -> Context + Send + Sync>;
+
+/// A text-map propagator carrying registered properties across a [Context] boundary.
+///
+/// # Examples
+///
+/// ```rust
+/// use sleppa_context::Context;
+/// use sleppa_context::propagation::{Propagator, Injector, Extractor};
+/// use std::collections::HashMap;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct RequestId(String);
+///
+/// impl sleppa_context::propagation::PropagatableProperty for RequestId {
+///     const KEY: &'static str = "request-id";
+///
+///     fn to_text(&self) -> String {
+///         self.0.clone()
+///     }
+///
+///     fn from_text(text: &str) -> Option<Self> {
+///         Some(RequestId(text.to_owned()))
+///     }
+/// }
+///
+/// let propagator = Propagator::new().register::<RequestId>();
+///
+/// let context = Context::new().with_property(RequestId("abc-123".into()));
+///
+/// let mut carrier: HashMap<String, String> = HashMap::new();
+/// propagator.inject(&context, &mut carrier);
+///
+/// let extracted = propagator.extract(&carrier);
+/// assert_eq!(extracted.get::<RequestId>(), Some(&RequestId("abc-123".into())));
+/// ```
+#[derive(Default)]
+pub struct Propagator {
+    injectors: Vec<InjectFn>,
+    extractors: Vec<ExtractFn>,
+}
+
+impl Propagator {
+    /// Creates a propagator with no registered property types.
+    pub fn new() -> Self {
+        Propagator::default()
+    }
+
+    /// Registers a property type so it is carried over by [`inject`](Propagator::inject) and
+    /// [`extract`](Propagator::extract).
+    pub fn register<T: PropagatableProperty>(mut self) -> Self {
+        self.injectors.push(Box::new(|cx, carrier| {
+            if let Some(property) = cx.get::<T>() {
+                carrier.set(T::KEY, property.to_text());
+            }
+        }));
+
+        self.extractors.push(Box::new(|carrier, cx| {
+            match carrier.get(T::KEY).and_then(T::from_text) {
+                Some(property) => cx.with_property(property),
+                None => cx,
+            }
+        }));
+
+        self
+    }
+
+    /// Writes every registered property found on `cx` into `carrier`.
+    pub fn inject(&self, cx: &Context, carrier: &mut dyn Injector) {
+        for injector in &self.injectors {
+            injector(cx, carrier);
+        }
+    }
+
+    /// Builds a new [Context] out of every registered property found on `carrier`.
+    pub fn extract(&self, carrier: &dyn Extractor) -> Context {
+        self.extractors
+            .iter()
+            .fold(Context::new(), |cx, extractor| extractor(carrier, cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct UserId(u64);
+
+    impl PropagatableProperty for UserId {
+        const KEY: &'static str = "user-id";
+
+        fn to_text(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn from_text(text: &str) -> Option<Self> {
+            text.parse().ok().map(UserId)
+        }
+    }
+
+    #[test]
+    fn test_inject_then_extract_round_trips_registered_property() {
+        let propagator = Propagator::new().register::<UserId>();
+        let context = Context::new().with_property(UserId(42));
+
+        let mut carrier: HashMap<String, String> = HashMap::new();
+        propagator.inject(&context, &mut carrier);
+        assert_eq!(carrier.get("user-id").map(String::as_str), Some("42"));
+
+        let extracted = propagator.extract(&carrier);
+        assert_eq!(extracted.get::<UserId>(), Some(&UserId(42)));
+    }
+
+    #[test]
+    fn test_extract_ignores_unregistered_and_malformed_entries() {
+        let propagator = Propagator::new().register::<UserId>();
+
+        let mut carrier: HashMap<String, String> = HashMap::new();
+        carrier.set("user-id", "not-a-number".to_owned());
+        carrier.set("request-id", "abc".to_owned());
+
+        let extracted = propagator.extract(&carrier);
+        assert_eq!(extracted.get::<UserId>(), None);
+        assert!(extracted.is_empty());
+    }
+}